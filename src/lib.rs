@@ -1,225 +1,541 @@
-#![feature(once_cell)]
-#![feature(cell_update)]
-
-//! Dependency-Free Iterator Extension Trait with accompanying struct to make !Clone Iterators with Clone elements into a Clone Iterator 
+//! Dependency-Free Iterator Extension Trait with accompanying struct to make !Clone Iterators with Clone elements into a Clone Iterator
 
 mod symbol {
-    use std::lazy::SyncLazy;
+    use std::sync::atomic::{AtomicU64, Ordering};
     use std::sync::Mutex;
-    use std::cell::Cell;
-    #[derive(Default, Clone, Copy)]
-    struct CounterStruct([u128;16]);
-    
-    static GLOBAL_SYMBOL_COUNTER: SyncLazy<Mutex<Cell<CounterStruct>>> = SyncLazy::new(|| Mutex::new(Cell::new(CounterStruct::default())));
-
-    impl CounterStruct {
-        fn get_and_add_one(&mut self) -> [u128;16] {
-            let mut add_to_position = Some(0);
-            let mut last_pos_fetched = 0;
-            let mut output = [0;16];
-            while let Some(pos) = add_to_position {
-                match (self.0)[pos] {
-                    ref mut val if *val == u128::MAX => {
-                        output[pos] = *val;
-                        *val = 0;
-                        add_to_position = Some(pos + 1);
-                    },
-                    ref mut val => {
-                        output[pos] = *val;
-                        *val += 1;
-                        last_pos_fetched = pos;
-                        add_to_position = None;
-                    }
-                }
-            }
-            for pos in (last_pos_fetched + 1)..output.len() {
-                output[pos] = (self.0)[pos];
-                (self.0)[pos] += 1
-            }
-            output
-        }
-    }
 
-    #[derive(Debug,Hash,PartialEq, Eq, Clone)]
-    pub struct Symbol([u128;16]);
+    /// The next `Symbol` to hand out if `FREE_LIST` is empty. `Symbol`s only need to be unique
+    /// among currently-live receivers, so once one is released its value can simply be recycled
+    /// instead of growing this counter forever.
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+    static FREE_LIST: Mutex<Vec<u64>> = Mutex::new(Vec::new());
+
+    #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+    pub struct Symbol(u64);
 
     impl Symbol {
         pub fn new() -> Self {
-            let mut output = [0;16];
-            GLOBAL_SYMBOL_COUNTER.lock().unwrap().update(|mut elm| {
-                output = elm.get_and_add_one();
-                elm
-            });
-            Symbol(output)
+            match FREE_LIST.lock().unwrap().pop() {
+                Some(id) => Symbol(id),
+                None => Symbol(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+            }
+        }
+
+        /// Releases this id back to the free list, so a future `Symbol::new()` can recycle it
+        /// instead of allocating a new one.
+        pub(crate) fn release(self) {
+            FREE_LIST.lock().unwrap().push(self.0);
         }
     }
 }
 
+mod cell {
+    use std::cell::RefCell;
+    use std::rc::{Rc, Weak as RcWeak};
+    use std::sync::{Arc, Condvar, Mutex, Weak as ArcWeak};
+
+    /// Abstracts over a shared, mutably-accessible handle to a `T`.
+    ///
+    /// `Clonable` and `LocalClonable` both build on the same buffering logic; the only thing
+    /// that differs between them is *how* that shared state is stored and locked. Being generic
+    /// over `Cell` lets them share that logic instead of duplicating it per strategy.
+    pub trait Cell<T>: Clone + Sized {
+        /// A non-owning handle, so a receiver can refer back to its bus without keeping it alive
+        /// on its own.
+        type Weak: Clone;
+
+        fn new(value: T) -> Self;
+        fn with_mut<R>(&self, f: impl FnOnce(&mut T) -> R) -> R;
+        fn downgrade(&self) -> Self::Weak;
+        fn upgrade(weak: &Self::Weak) -> Option<Self>;
+
+        /// Wakes anyone blocked waiting for this cell to change. A no-op unless the strategy
+        /// backs it with something to wait on, like [`Notified`].
+        fn notify(&self) {}
+    }
+
+    impl<T> Cell<T> for Arc<Mutex<T>> {
+        type Weak = ArcWeak<Mutex<T>>;
+
+        fn new(value: T) -> Self {
+            Arc::new(Mutex::new(value))
+        }
+        fn with_mut<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+            f(&mut self.lock().unwrap())
+        }
+        fn downgrade(&self) -> Self::Weak {
+            Arc::downgrade(self)
+        }
+        fn upgrade(weak: &Self::Weak) -> Option<Self> {
+            weak.upgrade()
+        }
+    }
+
+    impl<T> Cell<T> for Rc<RefCell<T>> {
+        type Weak = RcWeak<RefCell<T>>;
+
+        fn new(value: T) -> Self {
+            Rc::new(RefCell::new(value))
+        }
+        fn with_mut<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+            f(&mut self.borrow_mut())
+        }
+        fn downgrade(&self) -> Self::Weak {
+            Rc::downgrade(self)
+        }
+        fn upgrade(weak: &Self::Weak) -> Option<Self> {
+            weak.upgrade()
+        }
+    }
+
+    /// Like `Arc<Mutex<T>>`, but paired with a `Condvar` so a blocked reader can wait for the
+    /// value to change instead of spinning.
+    pub struct Notified<T> {
+        state: Arc<(Mutex<T>, Condvar)>
+    }
+
+    impl<T> Clone for Notified<T> {
+        fn clone(&self) -> Self {
+            Notified { state: Arc::clone(&self.state) }
+        }
+    }
+
+    impl<T> Cell<T> for Notified<T> {
+        type Weak = ArcWeak<(Mutex<T>, Condvar)>;
+
+        fn new(value: T) -> Self {
+            Notified { state: Arc::new((Mutex::new(value), Condvar::new())) }
+        }
+        fn with_mut<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+            f(&mut self.state.0.lock().unwrap())
+        }
+        fn downgrade(&self) -> Self::Weak {
+            Arc::downgrade(&self.state)
+        }
+        fn upgrade(weak: &Self::Weak) -> Option<Self> {
+            weak.upgrade().map(|state| Notified { state })
+        }
+        fn notify(&self) {
+            self.state.1.notify_all();
+        }
+    }
+
+    impl<T> Notified<T> {
+        /// Blocks until `pred` returns `Some`, re-checking each time the `Condvar` is notified.
+        pub fn wait_while<R>(&self, mut pred: impl FnMut(&mut T) -> Option<R>) -> R {
+            let mut guard = self.state.0.lock().unwrap();
+            loop {
+                if let Some(r) = pred(&mut guard) {
+                    return r;
+                }
+                guard = self.state.1.wait(guard).unwrap();
+            }
+        }
+    }
+
+    /// A compile-time choice of interior-mutability strategy for sharing state between a
+    /// `Clonable`-like type and its clones.
+    pub trait Strategy {
+        type Handle<X>: Cell<X>;
+    }
+
+    /// Shares state across threads, via [`Notified`] (`Arc`, `Mutex`, `Condvar`).
+    pub struct Threaded;
+    impl Strategy for Threaded {
+        type Handle<X> = Notified<X>;
+    }
+
+    /// Shares state on a single thread, via `Rc<RefCell<_>>`, with no atomics or locking.
+    pub struct Local;
+    impl Strategy for Local {
+        type Handle<X> = Rc<RefCell<X>>;
+    }
+}
 mod bus {
-    use std::sync::mpsc;
-    use std::collections::HashMap;
+    use std::collections::{HashMap, VecDeque};
+    use super::cell::{Cell, Notified};
     use super::symbol::Symbol;
 
-    pub struct Bus<T: Clone> {
-        senders: HashMap<Symbol,mpsc::Sender<T>>
+    /// The backing store for a `Clonable` and every clone descended from it.
+    ///
+    /// Rather than giving each clone its own private copy of the stream, `Bus` keeps a single
+    /// `VecDeque` of items that have been produced but not yet seen by every live clone. `base`
+    /// is the absolute index of `buffer[0]`, and `cursors` tracks, per clone, the absolute index
+    /// of the next item that clone hasn't read yet. An item can be evicted from the front as
+    /// soon as every live cursor has moved past it, so memory is bounded by the spread between
+    /// the fastest and the slowest clone rather than by the total length of the stream.
+    /// `finished` is set once the underlying iterator is known to be exhausted, so blocked
+    /// waiters know to stop waiting for items that will never arrive.
+    pub struct Bus<T> {
+        buffer: VecDeque<T>,
+        base: usize,
+        cursors: HashMap<Symbol, usize>,
+        finished: bool
     }
 
-    impl<T: Clone> Default for Bus<T> {
+    impl<T> Default for Bus<T> {
         fn default() -> Self {
-            Bus { senders: HashMap::new() }
+            Bus { buffer: VecDeque::new(), base: 0, cursors: HashMap::new(), finished: false }
         }
     }
 
     impl<T: Clone> Bus<T> {
-        pub fn broadcast(&mut self, val: T) -> Result<(),Vec<mpsc::SendError<T>>> {
-            let mut errors = vec![];
-            let mut iter = self.senders.values().peekable();
-            let mut last_sender = None;
-            while let Some(sender) = iter.next() {
-                match iter.peek() {
-                    Some(_) => match sender.send(val.clone()) {
-                        Ok(_) => (),
-                        Err(e) => errors.push(e)
-                    },
-                    None => {
-                        last_sender = Some(sender)
-                    }
-                };
-            }
-            match last_sender {
-                Some(sender) => match sender.send(val.clone()) {
-                    Ok(_) => (),
-                    Err(e) => errors.push(e)
-                },
-                None => ()
-            };
-            if errors.len() > 0 {
-                Err(errors)
-            } else {
-                Ok(())
-            }
+        fn push(&mut self, val: T) {
+            self.buffer.push_back(val);
+            self.evict();
         }
-        pub fn add_rx(&mut self) -> BusReceiver<T> {
-            let (new_sender,new_receiver) = mpsc::channel();
-            let new_id = Symbol::new();
-            self.senders.insert(new_id.clone(), new_sender);
-            BusReceiver {
-                receiver: new_receiver,
-                id: new_id
+
+        fn read(&mut self, id: &Symbol) -> Option<T> {
+            let cursor = self.cursors[id];
+            let val = self.buffer.get(cursor - self.base).cloned();
+            if val.is_some() {
+                *self.cursors.get_mut(id).unwrap() += 1;
+                self.evict();
             }
+            val
         }
-        pub fn branch_off(&mut self, prev: &BusReceiver<T>) -> BusReceiver<T> {
-            let (new_sender,new_receiver) = mpsc::channel();
-            let new_id = Symbol::new();
 
-            let mut stored_items = vec![];
-            for item in prev.receiver.try_iter() {
-                stored_items.push(item.clone());
-                new_sender.send(item).unwrap();
-            }
-            let prev_sender = self.senders.get_mut(&prev.id).unwrap();
-            for item in stored_items {
-                prev_sender.send(item).unwrap();
-            }
-            self.senders.insert(new_id.clone(), new_sender);
+        fn remove(&mut self, id: &Symbol) {
+            self.cursors.remove(id);
+            self.evict();
+        }
 
-            BusReceiver {
-                receiver: new_receiver,
-                id: new_id
+        fn evict(&mut self) {
+            let min_cursor = self.cursors.values().copied().min().unwrap_or(self.base + self.buffer.len());
+            while self.base < min_cursor {
+                self.buffer.pop_front();
+                self.base += 1;
             }
         }
     }
 
-    pub struct BusReceiver<T: Clone> {
-        receiver: mpsc::Receiver<T>,
-        id: Symbol
+    /// Registers a brand new receiver, starting at the current end of the stream.
+    pub fn add_rx<T: Clone, C: Cell<Bus<T>>>(bus: &C) -> BusReceiver<T, C> {
+        let id = Symbol::new();
+        bus.with_mut(move |b| {
+            let cursor = b.base + b.buffer.len();
+            b.cursors.insert(id, cursor);
+        });
+        BusReceiver::new(id, bus.downgrade())
     }
 
-    impl<T: Clone> BusReceiver<T> {
-        pub fn try_recv(&self) -> Result<T,mpsc::TryRecvError> {
-            self.receiver.try_recv()
+    /// Registers a clone of `prev`, starting wherever `prev` currently is.
+    pub fn branch_off<T: Clone, C: Cell<Bus<T>>>(bus: &C, prev: &BusReceiver<T, C>) -> BusReceiver<T, C> {
+        let id = Symbol::new();
+        bus.with_mut(move |b| {
+            let cursor = b.cursors[&prev.id];
+            b.cursors.insert(id, cursor);
+        });
+        BusReceiver::new(id, bus.downgrade())
+    }
+
+    /// Appends a freshly produced item, evicts anything every live cursor has passed, and wakes
+    /// anyone blocked waiting for new items.
+    pub fn push<T: Clone, C: Cell<Bus<T>>>(bus: &C, val: T) {
+        bus.with_mut(|b| b.push(val));
+        bus.notify();
+    }
+
+    /// Returns `receiver`'s next item if it's already buffered, without blocking.
+    pub fn read<T: Clone, C: Cell<Bus<T>>>(bus: &C, receiver: &BusReceiver<T, C>) -> Option<T> {
+        bus.with_mut(|b| b.read(&receiver.id))
+    }
+
+    /// Marks the underlying iterator as exhausted and wakes any blocked readers, so they stop
+    /// waiting for items that will never come.
+    pub fn finish<T: Clone, C: Cell<Bus<T>>>(bus: &C) {
+        bus.with_mut(|b| b.finished = true);
+        bus.notify();
+    }
+
+    /// Blocks the calling thread until `receiver` has an item buffered or the underlying
+    /// iterator is exhausted. Only meaningful for the `Notified` (cross-thread) strategy.
+    pub fn read_blocking<T: Clone>(bus: &Notified<Bus<T>>, receiver: &BusReceiver<T, Notified<Bus<T>>>) -> Option<T> {
+        bus.wait_while(|b| match b.read(&receiver.id) {
+            Some(val) => Some(Some(val)),
+            None if b.finished => Some(None),
+            None => None
+        })
+    }
+
+    /// The number of items currently buffered, i.e. not yet evicted. Exposed for tests that
+    /// check eviction actually bounds memory rather than just checking observable `next()` output.
+    #[cfg(test)]
+    pub(crate) fn buffered_len<T: Clone, C: Cell<Bus<T>>>(bus: &C) -> usize {
+        bus.with_mut(|b| b.buffer.len())
+    }
+
+    pub struct BusReceiver<T: Clone, C: Cell<Bus<T>>> {
+        id: Symbol,
+        handle: C::Weak
+    }
+
+    impl<T: Clone, C: Cell<Bus<T>>> BusReceiver<T, C> {
+        fn new(id: Symbol, handle: C::Weak) -> Self {
+            BusReceiver { id, handle }
+        }
+    }
+
+    impl<T: Clone, C: Cell<Bus<T>>> Drop for BusReceiver<T, C> {
+        fn drop(&mut self) {
+            if let Some(bus) = C::upgrade(&self.handle) {
+                bus.with_mut(|b| b.remove(&self.id));
+            }
+            self.id.release();
         }
     }
 }
 mod clonable_iterator {
-    use std::sync::Mutex;
-    use std::sync::Arc;
-    struct ClonableIteratorOwner<T: Iterator> where T::Item: Clone{
-        inner: T,
-        sender: super::bus::Bus<T::Item>
-    }
-    impl<T: Iterator> ClonableIteratorOwner<T> where T::Item: Clone{
-        fn produce(&mut self) {
-            match self.inner.next() {
-                Some(val) => { self.sender.broadcast(val).ok(); },
-                None => ()
+    use super::bus::{self, Bus, BusReceiver};
+    use super::cell::{Cell, Local, Strategy, Threaded};
+
+    struct ClonableCore<T: Iterator, S: Strategy> where T::Item: Clone {
+        owner: S::Handle<T>,
+        bus: S::Handle<Bus<T::Item>>,
+        receiver: BusReceiver<T::Item, S::Handle<Bus<T::Item>>>
+    }
+
+    impl<T: Iterator, S: Strategy> ClonableCore<T, S> where T::Item: Clone {
+        fn new(inner: T) -> Self {
+            let bus = <S::Handle<Bus<T::Item>> as Cell<Bus<T::Item>>>::new(Bus::default());
+            let receiver = bus::add_rx(&bus);
+            ClonableCore {
+                owner: <S::Handle<T> as Cell<T>>::new(inner),
+                bus,
+                receiver
             }
         }
     }
+
+    impl<T: Iterator, S: Strategy> Clone for ClonableCore<T, S> where T::Item: Clone {
+        fn clone(&self) -> Self {
+            let receiver = bus::branch_off(&self.bus, &self.receiver);
+            ClonableCore {
+                owner: self.owner.clone(),
+                bus: self.bus.clone(),
+                receiver
+            }
+        }
+    }
+
+    impl<T: Iterator, S: Strategy> Iterator for ClonableCore<T, S> where T::Item: Clone {
+        type Item = T::Item;
+        fn next(&mut self) -> Option<Self::Item> {
+            if let Some(val) = bus::read(&self.bus, &self.receiver) {
+                return Some(val);
+            }
+            // `inner.next()` and the resulting `push`/`finish` must happen as one step under the
+            // owner's lock: otherwise two threads racing to produce could each call `inner.next()`
+            // before either publishes, then publish in the opposite order, reordering the stream.
+            let bus = &self.bus;
+            let produced = self.owner.with_mut(|inner| match inner.next() {
+                Some(val) => {
+                    bus::push(bus, val);
+                    true
+                },
+                None => {
+                    bus::finish(bus);
+                    false
+                }
+            });
+            if produced {
+                bus::read(&self.bus, &self.receiver)
+            } else {
+                None
+            }
+        }
+    }
+
     /// A Clonable wrapper for any Iterator
     /// Any Iterator can be made into a Clonable, as long as the items they produce are themselves Clone.
     /// Note that the Iterator itself doesn't need to be Clone.
-    /// 
+    ///
     /// The clones of this iterator will start wherever the parent iterator left off, and the
     /// parent iterator will not be affected by this.
-    /// 
+    ///
+    /// Produced items are kept in a single shared buffer rather than duplicated per clone: each
+    /// clone only tracks its own position in that buffer, and an item is dropped from the buffer
+    /// once every live clone has moved past it. Memory use is therefore bounded by the gap
+    /// between the fastest and the slowest clone, not by the length of the stream. Dropping a
+    /// clone deregisters its place in the buffer automatically, via `BusReceiver`'s own `Drop`.
+    ///
     /// The original Iterator will be held in a Mutex, for concurrent access, which will itself be held
     /// inside an Arc, for shared ownership. The Original Iterator will be dropped whenever all clones
-    /// stemming from it.
-    pub struct Clonable<T: Iterator> where T::Item: Clone {
-        owner: Arc<Mutex<ClonableIteratorOwner<T>>>,
-        receiver: super::bus::BusReceiver<T::Item>
-    }
+    /// stemming from it. For the single-threaded case, see [`LocalClonable`].
+    pub struct Clonable<T: Iterator>(ClonableCore<T, Threaded>) where T::Item: Clone;
 
     impl<T: Iterator> Clonable<T> where T::Item: Clone {
         fn new(inner: T) -> Self {
-            let mut sender = super::bus::Bus::default();
-            let receiver = sender.add_rx();
-            Clonable {
-                owner: Arc::new(Mutex::new(ClonableIteratorOwner {
-                    inner,
-                    sender
-                })),
-                receiver
-            }
+            Clonable(ClonableCore::new(inner))
+        }
+
+        /// Blocks the calling thread until an item is available or the underlying iterator is
+        /// exhausted, instead of opportunistically producing one itself.
+        ///
+        /// Useful when several clones are split across threads and only one of them is meant to
+        /// drive the underlying iterator: the rest park on this call rather than spin on `next`.
+        pub fn next_blocking(&mut self) -> Option<T::Item> {
+            bus::read_blocking(&self.0.bus, &self.0.receiver)
+        }
+
+        /// Wraps this `Clonable` in an iterator whose `next` calls [`Self::next_blocking`].
+        pub fn blocking(self) -> Blocking<T> {
+            Blocking(self)
+        }
+
+        #[cfg(test)]
+        pub(crate) fn buffered_len(&self) -> usize {
+            bus::buffered_len(&self.0.bus)
         }
     }
 
     impl<T: Iterator> Clone for Clonable<T> where T::Item: Clone {
         fn clone(&self) -> Self {
-            let receiver = self.owner.lock().unwrap().sender.branch_off(&self.receiver);
-            Clonable {
-                owner: Arc::clone(&self.owner),
-                receiver
-            }
+            Clonable(self.0.clone())
         }
     }
 
-    /// Extension trait on all Iterators that adds a single `clonable` method
-    pub trait IterExt: Iterator + Sized where Self::Item: Clone {
-        /// This method consumes the iterator and produces a Clonable
-        fn clonable(self) -> Clonable<Self> {
-            Clonable::new(self)
+    impl<T: Iterator> Iterator for Clonable<T> where T::Item: Clone {
+        type Item = T::Item;
+        fn next(&mut self) -> Option<Self::Item> {
+            self.0.next()
         }
     }
 
-    impl<T: Iterator> IterExt for T where T::Item: Clone{}
+    /// An adapter, produced by [`Clonable::blocking`], whose `next` blocks on the shared buffer
+    /// rather than producing an item itself.
+    pub struct Blocking<T: Iterator>(Clonable<T>) where T::Item: Clone;
 
-    impl<T: Iterator> Iterator for Clonable<T> where T::Item: Clone {
+    impl<T: Iterator> Iterator for Blocking<T> where T::Item: Clone {
         type Item = T::Item;
         fn next(&mut self) -> Option<Self::Item> {
-            match self.receiver.try_recv() {
-                Ok(val) => Some(val),
-                Err(_) => {
-                    self.owner.lock().unwrap().produce();
-                    match self.receiver.try_recv() {
-                        Ok(val) => Some(val),
-                        Err(_) => None
-                    }
-                }
-            }
+            self.0.next_blocking()
+        }
+    }
+
+    /// A single-threaded counterpart to [`Clonable`].
+    ///
+    /// Shares the same buffering and branch-off semantics, but is built on `Rc<RefCell<_>>`
+    /// instead of `Arc<Mutex<_>>`, so `next` pays no locking or atomic-refcounting overhead.
+    /// Creating or dropping one still takes or returns its receiver id through the global,
+    /// mutex-guarded `Symbol` allocator. As with `Rc`, it can't be sent across threads, and it
+    /// has no blocking equivalent of [`Clonable::next_blocking`] since there would be nothing
+    /// else to wake it.
+    pub struct LocalClonable<T: Iterator>(ClonableCore<T, Local>) where T::Item: Clone;
+
+    impl<T: Iterator> LocalClonable<T> where T::Item: Clone {
+        fn new(inner: T) -> Self {
+            LocalClonable(ClonableCore::new(inner))
         }
     }
+
+    impl<T: Iterator> Clone for LocalClonable<T> where T::Item: Clone {
+        fn clone(&self) -> Self {
+            LocalClonable(self.0.clone())
+        }
+    }
+
+    impl<T: Iterator> Iterator for LocalClonable<T> where T::Item: Clone {
+        type Item = T::Item;
+        fn next(&mut self) -> Option<Self::Item> {
+            self.0.next()
+        }
+    }
+
+    /// Extension trait on all Iterators that adds `clonable`/`clonable_local` methods
+    pub trait IterExt: Iterator + Sized where Self::Item: Clone {
+        /// This method consumes the iterator and produces a `Clonable`, shareable across threads
+        fn clonable(self) -> Clonable<Self> {
+            Clonable::new(self)
+        }
+
+        /// Like [`Self::clonable`], but for single-threaded use: `next` takes no locks and uses no atomics
+        fn clonable_local(self) -> LocalClonable<Self> {
+            LocalClonable::new(self)
+        }
+    }
+
+    impl<T: Iterator> IterExt for T where T::Item: Clone{}
 }
 pub use self::clonable_iterator::IterExt;
 pub use self::clonable_iterator::Clonable;
+pub use self::clonable_iterator::LocalClonable;
+pub use self::clonable_iterator::Blocking;
+
+#[cfg(test)]
+mod tests {
+    use super::IterExt;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn clone_starts_where_parent_left_off() {
+        let mut original = (0..5).clonable();
+        assert_eq!(original.next(), Some(0));
+        assert_eq!(original.next(), Some(1));
+
+        let mut cloned = original.clone();
+        assert_eq!(cloned.next(), Some(2));
+        assert_eq!(original.next(), Some(2));
+
+        assert_eq!(cloned.collect::<Vec<_>>(), vec![3, 4]);
+        assert_eq!(original.collect::<Vec<_>>(), vec![3, 4]);
+    }
+
+    #[test]
+    fn local_clone_starts_where_parent_left_off() {
+        let mut original = (0..4).clonable_local();
+        assert_eq!(original.next(), Some(0));
+
+        let cloned = original.clone();
+        assert_eq!(original.collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(cloned.collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn dropping_a_lagging_clone_evicts_its_backlog() {
+        let mut fast = (0..1000).clonable();
+        let lagging = fast.clone();
+
+        fast.by_ref().take(1000).for_each(drop);
+        assert_eq!(fast.buffered_len(), 1000, "lagging clone hasn't read anything, so nothing can be evicted yet");
+
+        drop(lagging);
+        assert_eq!(fast.buffered_len(), 0, "dropping the only other cursor should evict the whole backlog");
+    }
+
+    #[test]
+    fn blocking_next_wakes_across_threads() {
+        let mut producer = (0..5).clonable();
+        let mut blocking_consumer = producer.clone().blocking();
+
+        let consumer = thread::spawn(move || {
+            (0..6).map(|_| blocking_consumer.next()).collect::<Vec<_>>()
+        });
+
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(producer.by_ref().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+
+        assert_eq!(
+            consumer.join().unwrap(),
+            vec![Some(0), Some(1), Some(2), Some(3), Some(4), None]
+        );
+    }
+
+    #[test]
+    fn concurrent_producers_do_not_reorder_items() {
+        let verifier = (0..2000).clonable();
+        let racers: Vec<_> = (0..8).map(|_| verifier.clone()).collect();
+
+        let handles: Vec<_> = racers
+            .into_iter()
+            .map(|mut racer| thread::spawn(move || while racer.next().is_some() {}))
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(verifier.collect::<Vec<_>>(), (0..2000).collect::<Vec<_>>());
+    }
+}